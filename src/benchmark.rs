@@ -0,0 +1,118 @@
+//! Small benchmark harness for comparing the sequential `iter_mut` path
+//! against rayon's `par_iter_mut` path on a grid update, and for sweeping
+//! problem sizes to find the point where parallelism pays off.
+
+use rayon::prelude::*;
+use std::time::{Duration, Instant};
+
+/// min/median/mean elapsed time over several repeated timings of one workload.
+#[derive(Debug)]
+pub struct TimingStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+fn summarize(mut samples: Vec<Duration>) -> TimingStats {
+    samples.sort();
+    let min = samples[0];
+    let median = samples[samples.len() / 2];
+    let mean = samples.iter().sum::<Duration>() / samples.len() as u32;
+    TimingStats { min, median, mean }
+}
+
+/// Times `repeats` sequential `iter_mut` runs of `work` over `len` elements.
+fn time_sequential<F>(len: usize, repeats: usize, work: &F) -> TimingStats
+where
+    F: Fn(usize) -> usize + Sync,
+{
+    let mut samples = Vec::with_capacity(repeats);
+    for _ in 0..repeats {
+        let mut array: Vec<_> = (0..len).collect();
+        let now = Instant::now();
+        array.iter_mut().enumerate().for_each(|(i, v)| *v = work(i));
+        samples.push(now.elapsed());
+    }
+    summarize(samples)
+}
+
+/// Times `repeats` parallel `par_iter_mut` runs of `work` over `len`
+/// elements. When `min_len` is `Some`, each thread is handed at least that
+/// many elements via `with_min_len`; otherwise rayon's default chunking applies.
+fn time_parallel<F>(len: usize, repeats: usize, min_len: Option<usize>, work: &F) -> TimingStats
+where
+    F: Fn(usize) -> usize + Sync,
+{
+    let mut samples = Vec::with_capacity(repeats);
+    for _ in 0..repeats {
+        let mut array: Vec<_> = (0..len).collect();
+        let now = Instant::now();
+        match min_len {
+            Some(min_len) => array.par_iter_mut()
+                .with_min_len(min_len)
+                .enumerate()
+                .for_each(|(i, v)| *v = work(i)),
+            None => array.par_iter_mut().enumerate().for_each(|(i, v)| *v = work(i)),
+        }
+        samples.push(now.elapsed());
+    }
+    summarize(samples)
+}
+
+/// Result of comparing the sequential and parallel paths over `repeats` runs.
+#[derive(Debug)]
+pub struct BenchResult {
+    pub n_row: usize,
+    pub n_col: usize,
+    pub sequential: TimingStats,
+    pub parallel: TimingStats,
+}
+
+impl BenchResult {
+    /// Speedup of the parallel path's median over the sequential path's median.
+    pub fn speedup(&self) -> f64 {
+        self.sequential.median.as_secs_f64() / self.parallel.median.as_secs_f64()
+    }
+}
+
+/// Runs the sequential (`iter_mut`) and parallel (`par_iter_mut`) update of an
+/// `n_row x n_col` grid `repeats` times each, applying `work` to every cell's
+/// linear index, and reports min/median/mean elapsed time for both paths.
+///
+/// Panics if `repeats` is `0`, since there would be no samples to summarize.
+pub fn benchmark_grid_update<F>(n_row: usize, n_col: usize, repeats: usize, work: F) -> BenchResult
+where
+    F: Fn(usize) -> usize + Sync,
+{
+    assert!(repeats > 0, "repeats must be greater than 0");
+    let len = n_row * n_col;
+
+    BenchResult {
+        n_row,
+        n_col,
+        sequential: time_sequential(len, repeats, &work),
+        parallel: time_parallel(len, repeats, None, &work),
+    }
+}
+
+/// Sweeps `sizes` (element counts) in the order given and returns the first
+/// one at which the parallel path's median beats the sequential path's
+/// median; for ascending `sizes` that is the smallest such size. `min_len`
+/// controls the minimum chunk rayon hands each thread via `with_min_len`,
+/// since coarser chunking raises the size needed to amortize its overhead.
+///
+/// Panics if `repeats` is `0`, since there would be no samples to summarize.
+pub fn find_crossover<F>(sizes: &[usize], repeats: usize, min_len: usize, work: F) -> Option<usize>
+where
+    F: Fn(usize) -> usize + Sync,
+{
+    assert!(repeats > 0, "repeats must be greater than 0");
+    for &len in sizes {
+        let sequential = time_sequential(len, repeats, &work);
+        let parallel = time_parallel(len, repeats, Some(min_len), &work);
+        if parallel.median < sequential.median {
+            return Some(len);
+        }
+    }
+    None
+}
@@ -0,0 +1,239 @@
+//! Hand-written iterator adapters mirroring the most useful `itertools`
+//! combinators, implemented purely on the `Iterator` trait so the tutorial
+//! stays self-contained without pulling in the itertools crate.
+
+/// Yields the separator between consecutive items of the wrapped iterator,
+/// buffering one peeked element and toggling an "emit separator next" flag
+/// so no separator is emitted before the first or after the last item.
+pub struct Intersperse<I: Iterator> {
+    iter: std::iter::Peekable<I>,
+    sep: I::Item,
+    emit_sep: bool,
+}
+
+impl<I: Iterator> Intersperse<I>
+where
+    I::Item: Clone,
+{
+    fn new(iter: I, sep: I::Item) -> Intersperse<I> {
+        Intersperse {
+            iter: iter.peekable(),
+            sep,
+            emit_sep: false,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Intersperse<I>
+where
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.emit_sep {
+            self.emit_sep = false;
+            return Some(self.sep.clone());
+        }
+        let item = self.iter.next()?;
+        self.emit_sep = self.iter.peek().is_some();
+        Some(item)
+    }
+}
+
+/// Holds one pending item and merges it with the next when `f` returns
+/// `Ok(merged)`; when `f` returns `Err((keep, stash))` the pending run is
+/// flushed as `keep` and `stash` becomes the new pending item.
+pub struct Coalesce<I: Iterator, F> {
+    iter: I,
+    f: F,
+    pending: Option<I::Item>,
+}
+
+impl<I, F> Coalesce<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item, I::Item) -> Result<I::Item, (I::Item, I::Item)>,
+{
+    fn new(mut iter: I, f: F) -> Coalesce<I, F> {
+        let pending = iter.next();
+        Coalesce { iter, f, pending }
+    }
+}
+
+impl<I, F> Iterator for Coalesce<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item, I::Item) -> Result<I::Item, (I::Item, I::Item)>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut current = self.pending.take()?;
+        loop {
+            match self.iter.next() {
+                Some(next_item) => match (self.f)(current, next_item) {
+                    Ok(merged) => current = merged,
+                    Err((keep, stash)) => {
+                        self.pending = Some(stash);
+                        return Some(keep);
+                    }
+                },
+                None => return Some(current),
+            }
+        }
+    }
+}
+
+/// Buffers items while `f` keeps returning an equal key, yielding each run
+/// as `(key, Vec<item>)`.
+pub struct ChunkBy<I: Iterator, F> {
+    iter: std::iter::Peekable<I>,
+    f: F,
+}
+
+impl<I, F, K> ChunkBy<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    fn new(iter: I, f: F) -> ChunkBy<I, F> {
+        ChunkBy {
+            iter: iter.peekable(),
+            f,
+        }
+    }
+}
+
+impl<I, F, K> Iterator for ChunkBy<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    type Item = (K, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.iter.next()?;
+        let key = (self.f)(&first);
+        let mut group = vec![first];
+        while let Some(peeked) = self.iter.peek() {
+            if (self.f)(peeked) == key {
+                group.push(self.iter.next().unwrap());
+            } else {
+                break;
+            }
+        }
+        Some((key, group))
+    }
+}
+
+pub trait ItertoolsAdaptersExt: Iterator + Sized {
+    // Named `intersperse_sep` rather than `intersperse` because the latter
+    // shadows the unstable `Iterator::intersperse` nightly has been trialing:
+    // calls would silently resolve to this trait method today but become
+    // an ambiguous/breaking call once std stabilizes its own.
+    fn intersperse_sep(self, sep: Self::Item) -> Intersperse<Self>
+    where
+        Self::Item: Clone,
+    {
+        Intersperse::new(self, sep)
+    }
+
+    fn coalesce<F>(self, f: F) -> Coalesce<Self, F>
+    where
+        F: FnMut(Self::Item, Self::Item) -> Result<Self::Item, (Self::Item, Self::Item)>,
+    {
+        Coalesce::new(self, f)
+    }
+
+    fn chunk_by<F, K>(self, f: F) -> ChunkBy<Self, F>
+    where
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
+    {
+        ChunkBy::new(self, f)
+    }
+
+    fn group_by<F, K>(self, f: F) -> ChunkBy<Self, F>
+    where
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
+    {
+        self.chunk_by(f)
+    }
+}
+
+impl<I: Iterator> ItertoolsAdaptersExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersperse_between_items() {
+        let v: Vec<_> = (1..=4).intersperse_sep(0).collect();
+        assert_eq!(v, vec![1, 0, 2, 0, 3, 0, 4]);
+    }
+
+    #[test]
+    fn intersperse_single_element() {
+        let v: Vec<_> = std::iter::once(1).intersperse_sep(0).collect();
+        assert_eq!(v, vec![1]);
+    }
+
+    #[test]
+    fn intersperse_empty() {
+        let v: Vec<i32> = std::iter::empty().intersperse_sep(0).collect();
+        assert_eq!(v, Vec::<i32>::new());
+    }
+
+    #[test]
+    fn coalesce_merges_adjacent_runs() {
+        let v: Vec<_> = vec![1, 1, 2, 3, 3, 3]
+            .into_iter()
+            .coalesce(|a, b| if a == b { Ok(a) } else { Err((a, b)) })
+            .collect();
+        assert_eq!(v, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn coalesce_single_element() {
+        let v: Vec<_> = vec![1]
+            .into_iter()
+            .coalesce(|a, b| if a == b { Ok(a) } else { Err((a, b)) })
+            .collect();
+        assert_eq!(v, vec![1]);
+    }
+
+    #[test]
+    fn coalesce_empty() {
+        let v: Vec<i32> = Vec::new()
+            .into_iter()
+            .coalesce(|a, b| if a == b { Ok(a) } else { Err((a, b)) })
+            .collect();
+        assert_eq!(v, Vec::<i32>::new());
+    }
+
+    #[test]
+    fn chunk_by_groups_runs() {
+        let v: Vec<_> = vec![1, 1, 2, 2, 2, 3]
+            .into_iter()
+            .chunk_by(|&x| x)
+            .collect();
+        assert_eq!(v, vec![(1, vec![1, 1]), (2, vec![2, 2, 2]), (3, vec![3])]);
+    }
+
+    #[test]
+    fn chunk_by_single_element() {
+        let v: Vec<_> = vec![1].into_iter().chunk_by(|&x| x).collect();
+        assert_eq!(v, vec![(1, vec![1])]);
+    }
+
+    #[test]
+    fn chunk_by_empty() {
+        let v: Vec<(i32, Vec<i32>)> = Vec::new().into_iter().chunk_by(|&x| x).collect();
+        assert_eq!(v, Vec::<(i32, Vec<i32>)>::new());
+    }
+}
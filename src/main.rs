@@ -1,6 +1,10 @@
-use rayon::prelude::*;
 use std::collections::HashMap;
-use std::time::Instant;
+use std::pin::Pin;
+
+mod itertools_adapters;
+use itertools_adapters::ItertoolsAdaptersExt;
+
+mod benchmark;
 
 fn main() {
 
@@ -10,7 +14,9 @@ fn main() {
     collect();
     transform_and_collect();
     string_manipulations();
+    owning_flat_map_demo();
     update_vector_with_iterators();
+    primes();
 
 }
 
@@ -55,6 +61,49 @@ fn one_to_ten() {
     }
 }
 
+struct Primes {
+    found: Vec<u64>,
+    candidate: u64,
+}
+
+impl Primes {
+    fn new() -> Primes {
+        Primes {
+            found: Vec::new(),
+            candidate: 1,
+        }
+    }
+}
+
+/// Generates primes indefinitely via an incremental sieve: `found` stays
+/// sorted and complete up to the last returned prime, so trial division
+/// against `found` (up to sqrt(candidate)) is enough to test the next
+/// candidate. This sidesteps nesting `filter` closures, which would need
+/// each closure to borrow the growing set of earlier primes.
+impl Iterator for Primes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.candidate += 1;
+            let is_prime = self.found.iter()
+                .take_while(|&&p| p*p <= self.candidate)
+                .all(|&p| !self.candidate.is_multiple_of(p));
+            if is_prime {
+                self.found.push(self.candidate);
+                return Some(self.candidate);
+            }
+        }
+    }
+}
+
+fn primes() {
+    // Primes::new() generates primes lazily, one at a time.
+    for p in Primes::new().take(10) {
+        println!("Prime: {}", p);
+    }
+}
+
 fn into_iter_vs_iter_vs_iter_mut() {
     let mut v = vec!['a','b','c'];
 
@@ -102,6 +151,26 @@ fn adapters() {
     for i in (1..=16).filter(|&x| x&1 == 1) {
         println!("Filter: {}", i);
     }
+
+    // intersperse_sep is a hand-written itertools-style adapter.
+    for i in (1..=5).intersperse_sep(0) {
+        println!("Intersperse: {}", i);
+    }
+
+    // coalesce merges adjacent equal runs into a single value.
+    for i in vec![1,1,2,3,3,3].into_iter().coalesce(|a,b| if a == b {Ok(a)} else {Err((a,b))}) {
+        println!("Coalesce: {}", i);
+    }
+
+    // chunk_by groups runs of equal keys into (key, Vec<item>) pairs.
+    for (key, group) in vec![1,1,2,2,2,3].into_iter().chunk_by(|&x| x) {
+        println!("ChunkBy: {} -> {:?}", key, group);
+    }
+
+    // group_by is an alias for chunk_by, matching itertools' older name.
+    for (key, group) in vec!['a','a','b'].into_iter().group_by(|&c| c) {
+        println!("GroupBy: {} -> {:?}", key, group);
+    }
 }
 
 fn collect() {
@@ -190,24 +259,144 @@ fn string_manipulations() {
     println!("{}", rev_s);
 }
 
+/// Adapter for the common pain point where `map` produces owned values
+/// (e.g. `String`) and a later `flat_map` wants to borrow from them: the
+/// borrow can't outlive the temporary the closure returned.
+///
+/// `OwningFlatMap` keeps the current owned item pinned on the heap in
+/// `stored`, so its address stays stable across calls even though `self`
+/// itself may move, and calls `f` on a reference to that pinned value to get
+/// `sub`, which it then drains with plain `Iterator::next` across repeated
+/// `next` calls instead of re-deriving it (and re-skipping already-yielded
+/// items) every time.
+///
+/// `f`'s signature, `F: FnMut(&I::Item) -> Box<dyn Iterator<Item = T> + '_>`,
+/// is what makes this sound: `T` is fixed once for the whole adapter, chosen
+/// independently of any particular call's borrow, so a closure can't smuggle
+/// a reference into `stored` out as `T` the way the first version of this
+/// adapter did by transmuting its borrow to `'static` (`T` would have to
+/// equal `&'r I::Item` for that call's anonymous `'r`, and `'r` isn't
+/// nameable outside the closure body, so the compiler rejects it). Only the
+/// trait object's own lifetime bound, not `T`, varies per call, which is
+/// exactly what lets `sub` legitimately borrow from `stored`. `sub` is
+/// always cleared before `stored` is replaced or dropped, so the erased
+/// borrow inside it is never used once it would dangle.
+struct OwningFlatMap<I, F, T>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> Box<dyn Iterator<Item = T> + '_>,
+{
+    sub: Option<Box<dyn Iterator<Item = T> + 'static>>,
+    stored: Option<Pin<Box<I::Item>>>,
+    inner: I,
+    f: F,
+}
+
+impl<I, F, T> OwningFlatMap<I, F, T>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> Box<dyn Iterator<Item = T> + '_>,
+{
+    fn new(inner: I, f: F) -> OwningFlatMap<I, F, T> {
+        OwningFlatMap {
+            sub: None,
+            stored: None,
+            inner,
+            f,
+        }
+    }
+}
+
+impl<I, F, T> Iterator for OwningFlatMap<I, F, T>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> Box<dyn Iterator<Item = T> + '_>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(sub) = self.sub.as_mut() {
+                if let Some(value) = sub.next() {
+                    return Some(value);
+                }
+                self.sub = None;
+                self.stored = None;
+            }
+            self.stored = Some(Box::pin(self.inner.next()?));
+            // SAFETY: `item_ref` borrows from `self.stored`, which stays put
+            // (pinned) and allocated until `self.sub` is cleared again, so
+            // the reference is valid for as long as `sub` (the only thing
+            // that uses it) exists; widening it to a nameable lifetime here
+            // only affects where the borrow is tracked, not the item type
+            // `T` that can escape through `sub`'s `next()` (see the type's
+            // doc comment for why `T` can't alias this borrow).
+            let item_ref: &I::Item = unsafe {
+                &*(self.stored.as_ref().unwrap().as_ref().get_ref() as *const I::Item)
+            };
+            let sub = (self.f)(item_ref);
+            let sub: Box<dyn Iterator<Item = T> + 'static> = unsafe { std::mem::transmute(sub) };
+            self.sub = Some(sub);
+        }
+    }
+}
+
+trait OwningFlatMapExt: Iterator + Sized {
+    fn owning_flat_map<F, T>(self, f: F) -> OwningFlatMap<Self, F, T>
+    where
+        F: FnMut(&Self::Item) -> Box<dyn Iterator<Item = T> + '_>,
+    {
+        OwningFlatMap::new(self, f)
+    }
+}
+
+impl<I: Iterator> OwningFlatMapExt for I {}
+
+fn greet(name: &&str) -> String {
+    format!("Hello, {}!", name)
+}
+
+fn owning_flat_map_demo() {
+    let names = ["alice", "bob", "carol"];
+
+    // map(greet) produces owned Strings; chaining .flat_map(str::chars)
+    // directly on that would try to borrow from a temporary. owning_flat_map
+    // keeps each String alive long enough to iterate its chars.
+    let chars: String = names.iter()
+        .map(greet)
+        .owning_flat_map(|s| Box::new(s.chars()))
+        .collect();
+    println!("{}", chars);
+}
+
 fn update_vector_with_iterators() {
     let n_col = 1000;
     let n_row = 1000;
-    let row = |p:usize|p/n_col;
-    let col = |p:usize|p%n_col;
-    let mut array: Vec<_> = (1..=n_row*n_col).collect();
-
-    // Use the sequential iterator iter_mut from rust
-    let now = Instant::now();
-    array.iter_mut().enumerate().for_each(|(i,v)|{
-        *v = row(i)*col(i);
-    });
-    println!("Sequential: {:?}",now.elapsed());
-
-    // Use the concurrent iterator par_iter_mut from rayon
-    let now = Instant::now();
-    array.par_iter_mut().enumerate().for_each(|(i,v)|{
-        *v = row(i)*col(i);
-    });
-    println!("Concurrent: {:?}",now.elapsed());
+    let row = |p: usize| p / n_col;
+    let col = |p: usize| p % n_col;
+
+    // Run both the sequential iter_mut path and the rayon par_iter_mut path
+    // several times and compare min/median/mean elapsed time and speedup,
+    // instead of relying on a single noisy measurement.
+    let result = benchmark::benchmark_grid_update(n_row, n_col, 5, |i| row(i) * col(i));
+    println!(
+        "Sequential ({}x{}): min={:?} median={:?} mean={:?}",
+        result.n_row, result.n_col,
+        result.sequential.min, result.sequential.median, result.sequential.mean,
+    );
+    println!(
+        "Parallel   ({}x{}): min={:?} median={:?} mean={:?}",
+        result.n_row, result.n_col,
+        result.parallel.min, result.parallel.median, result.parallel.mean,
+    );
+    println!("Speedup: {:.2}x", result.speedup());
+
+    // Sweep problem sizes to find the smallest one where par_iter_mut starts
+    // to beat iter_mut, using with_min_len to show how chunk granularity
+    // shifts that crossover point.
+    let sizes: Vec<usize> = (1..=10).map(|k| k * 100_000).collect();
+    match benchmark::find_crossover(&sizes, 5, 1_000, |i| i % 97) {
+        Some(len) => println!("Crossover at {} elements", len),
+        None => println!("No crossover found up to {} elements", sizes.last().unwrap()),
+    }
 }
\ No newline at end of file